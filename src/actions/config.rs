@@ -1,9 +1,39 @@
 use crate::error::Error as SystemError;
-use crate::settings::{CategoryConfig, ChannelConfig, Config, ServerConfig};
+use crate::settings::{
+    CategoryConfig, ChannelConfig, ChannelKind, Config, PermissionOverwriteConfig, RoleConfig,
+    ServerConfig,
+};
+use crate::Handler;
 use serenity::all::*;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
 use thiserror::Error;
 
+/// Maps our config-facing [`ChannelKind`] to serenity's [`ChannelType`].
+fn channel_type(kind: ChannelKind) -> ChannelType {
+    match kind {
+        ChannelKind::Text => ChannelType::Text,
+        ChannelKind::Voice => ChannelType::Voice,
+        ChannelKind::Announcement => ChannelType::News,
+        ChannelKind::Forum => ChannelType::Forum,
+        ChannelKind::Stage => ChannelType::Stage,
+    }
+}
+
+/// Maps serenity's [`ChannelType`] back to our config-facing [`ChannelKind`], for [`export`].
+///
+/// Channel types that the config format has no representation for (threads, DMs, etc.) are
+/// exported as text channels.
+fn channel_kind(kind: ChannelType) -> ChannelKind {
+    match kind {
+        ChannelType::Voice => ChannelKind::Voice,
+        ChannelType::News => ChannelKind::Announcement,
+        ChannelType::Forum => ChannelKind::Forum,
+        ChannelType::Stage => ChannelKind::Stage,
+        _ => ChannelKind::Text,
+    }
+}
+
 /// Represents all possible errors that can occur during the configuration process.
 #[derive(Debug, Error)]
 pub enum Error {
@@ -30,10 +60,68 @@ pub enum Error {
     /// Occurs when invalid data is provided.
     #[error("Invalid data: {0}")]
     InvalidData(String),
+
+    /// Occurs when a permission overwrite references a role that isn't in the config.
+    #[error("Unknown role: {0}")]
+    UnknownRole(String),
+}
+
+/// A summary of everything `run` did, for reporting back to the user.
+///
+/// Per-item failures are collected here rather than aborting the whole run on the first one.
+#[derive(Debug, Default, Clone)]
+pub struct ApplyReport {
+    pub categories_created: usize,
+    pub categories_updated: usize,
+    pub channels_created: usize,
+    pub channels_updated: usize,
+    pub pruned: usize,
+    pub failures: Vec<String>,
+}
+
+/// A single change between the live guild state and the config, as computed by [`diff`].
+///
+/// `run` applies these against the guild; `plan` renders them back to the user instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    CreateCategory(String),
+    UpdateCategory { name: String, fields: Vec<String> },
+    CreateChannel { category: String, name: String },
+    UpdateChannel { category: String, name: String, fields: Vec<String> },
+    Prune(String),
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::CreateCategory(name) => write!(f, "+ create category '{name}'"),
+            Change::UpdateCategory { name, fields } => {
+                write!(f, "~ update category '{name}' ({})", fields.join(", "))
+            }
+            Change::CreateChannel { category, name } => {
+                write!(f, "+ create channel '{category}/{name}'")
+            }
+            Change::UpdateChannel {
+                category,
+                name,
+                fields,
+            } => write!(
+                f,
+                "~ update channel '{category}/{name}' ({})",
+                fields.join(", ")
+            ),
+            Change::Prune(label) => write!(f, "- prune {label}"),
+        }
+    }
 }
 
 /// Creates or updates categories and channels in the guild according to the provided configuration.
-pub async fn run(config: Config, context: Context, guild_id: GuildId) -> Result<(), SystemError> {
+pub async fn run(
+    handler: &Handler,
+    config: Config,
+    context: Context,
+    guild_id: GuildId,
+) -> Result<ApplyReport, SystemError> {
     let mut guild = context
         .http
         .get_guild(guild_id)
@@ -41,18 +129,208 @@ pub async fn run(config: Config, context: Context, guild_id: GuildId) -> Result<
         .map_err(|_| Error::GuildNotFound)?
         .to_owned();
 
-    let mut channels = guild.channels(&context.http).await?;
+    let channels = fetch_channels(handler, &context, guild_id).await?;
 
     update_guild_info(&config.server, &context, &mut guild).await?;
-    process_categories(
-        &config.server.categories,
+    let role_ids = process_roles(&config.server, &context, &mut guild).await?;
+
+    let changes = diff(&config.server, &channels);
+    let mut report = apply_changes(
+        &changes,
+        &config.server,
         &context,
-        &mut channels,
         &mut guild,
+        &channels,
+        &role_ids,
     )
-    .await?;
+    .await;
 
-    Ok(())
+    if config.server.prune {
+        let (category_names, channel_keys) = config_keys(&config.server.categories);
+        let (pruned, failures) =
+            prune_channels(&context, &channels, &category_names, &channel_keys).await;
+        report.pruned += pruned;
+        report.failures.extend(failures);
+    }
+
+    // The run just created, updated, or pruned channels, so the cached view is now stale;
+    // evict it and let the next call repopulate from serenity's own gateway cache, or refetch.
+    handler.channel_cache.write().await.remove(&guild_id);
+
+    Ok(report)
+}
+
+/// Computes the changes `run` would apply for the given config, without touching the guild.
+pub async fn plan(
+    handler: &Handler,
+    config: &Config,
+    context: &Context,
+    guild_id: GuildId,
+) -> Result<Vec<Change>, SystemError> {
+    let channels = fetch_channels(handler, context, guild_id).await?;
+
+    Ok(diff(&config.server, &channels))
+}
+
+/// Fetches the guild's categories and channels, preferring serenity's gateway-populated cache
+/// over a fresh HTTP round-trip.
+///
+/// Falls back to the handler's own per-guild cache on a cache miss, and only reaches for the
+/// HTTP API if neither has a view of the guild yet, refreshing the handler's cache for next time.
+async fn fetch_channels(
+    handler: &Handler,
+    context: &Context,
+    guild_id: GuildId,
+) -> Result<HashMap<ChannelId, GuildChannel>, SystemError> {
+    if let Some(guild) = context.cache.guild(guild_id) {
+        let channels = guild.channels.clone();
+        handler
+            .channel_cache
+            .write()
+            .await
+            .insert(guild_id, channels.clone());
+        return Ok(channels);
+    }
+
+    if let Some(channels) = handler.channel_cache.read().await.get(&guild_id) {
+        return Ok(channels.clone());
+    }
+
+    let guild = context
+        .http
+        .get_guild(guild_id)
+        .await
+        .map_err(|_| Error::GuildNotFound)?;
+    let channels = guild.channels(&context.http).await?;
+
+    handler
+        .channel_cache
+        .write()
+        .await
+        .insert(guild_id, channels.clone());
+
+    Ok(channels)
+}
+
+/// Builds a [`Config`] reflecting the guild's current state, for the `~export` command.
+///
+/// Channels with no parent category are omitted, since the config format only supports
+/// categorized channels.
+pub async fn export(context: &Context, guild_id: GuildId) -> Result<Config, SystemError> {
+    let guild = context
+        .http
+        .get_guild(guild_id)
+        .await
+        .map_err(|_| Error::GuildNotFound)?;
+
+    let channels = guild.channels(&context.http).await?;
+
+    let role_names: HashMap<RoleId, String> = guild
+        .roles
+        .values()
+        .map(|role| (role.id, role.name.clone()))
+        .collect();
+
+    let roles = guild
+        .roles
+        .values()
+        .filter(|role| role.id.get() != guild_id.get())
+        .map(|role| {
+            (
+                role.name.clone(),
+                RoleConfig {
+                    color: Some(role.colour.0),
+                    hoist: Some(role.hoist),
+                    mentionable: Some(role.mentionable),
+                    permissions: Some(role.permissions.bits()),
+                },
+            )
+        })
+        .collect();
+
+    let categories = channels
+        .values()
+        .filter(|c| c.kind == ChannelType::Category)
+        .map(|category| {
+            let channel_configs = channels
+                .values()
+                .filter(|c| c.kind != ChannelType::Category && c.parent_id == Some(category.id))
+                .map(|channel| (channel.name.clone(), export_channel(channel, &role_names)))
+                .collect();
+
+            (
+                category.name.clone(),
+                CategoryConfig {
+                    channels: channel_configs,
+                    description: category.topic.clone(),
+                    nsfw: Some(category.nsfw),
+                    permissions: export_permissions(&category.permission_overwrites, &role_names),
+                },
+            )
+        })
+        .collect();
+
+    Ok(Config {
+        server: ServerConfig {
+            name: guild.name.clone(),
+            categories,
+            description: None,
+            icon_url: guild.icon_url(),
+            prune: false,
+            roles,
+        },
+    })
+}
+
+/// Builds the exported [`ChannelConfig`] for a single live channel.
+fn export_channel(channel: &GuildChannel, role_names: &HashMap<RoleId, String>) -> ChannelConfig {
+    ChannelConfig {
+        name: channel.name.clone(),
+        kind: channel_kind(channel.kind),
+        topic: channel.topic.clone(),
+        nsfw: Some(channel.nsfw),
+        position: Some(channel.position as u32),
+        parent_category: None,
+        bitrate: channel.bitrate,
+        user_limit: channel.user_limit,
+        permissions: export_permissions(&channel.permission_overwrites, role_names),
+    }
+}
+
+/// Converts a live channel or category's role-based permission overwrites back into the config's
+/// `role name -> overwrite` form. Member-specific overwrites have no place in the config and are
+/// dropped.
+fn export_permissions(
+    overwrites: &[PermissionOverwrite],
+    role_names: &HashMap<RoleId, String>,
+) -> HashMap<String, PermissionOverwriteConfig> {
+    overwrites
+        .iter()
+        .filter_map(|overwrite| {
+            let PermissionOverwriteType::Role(role_id) = overwrite.kind else {
+                return None;
+            };
+
+            let name = role_names.get(&role_id)?.clone();
+            Some((
+                name,
+                PermissionOverwriteConfig {
+                    allow: non_zero_bits(overwrite.allow.bits()),
+                    deny: non_zero_bits(overwrite.deny.bits()),
+                },
+            ))
+        })
+        .collect()
+}
+
+/// Returns `None` for an empty permission bitflag value, so exported configs omit the field
+/// instead of writing out an explicit zero.
+fn non_zero_bits(bits: u64) -> Option<u64> {
+    if bits == 0 {
+        None
+    } else {
+        Some(bits)
+    }
 }
 
 /// Updates the guild's name and icon if they differ from the configuration.
@@ -72,68 +350,503 @@ async fn update_guild_info(
     Ok(())
 }
 
-/// Processes categories by creating or updating them and then processes their channels.
-async fn process_categories(
-    categories: &HashMap<String, CategoryConfig>,
+/// Creates or updates guild roles from the config and resolves their names to [`RoleId`]s.
+///
+/// This must run before categories and channels are processed, since their permission
+/// overwrites reference roles by the same human-readable names used here.
+async fn process_roles(
+    server: &ServerConfig,
     context: &Context,
-    channels: &mut HashMap<ChannelId, GuildChannel>,
     guild: &mut PartialGuild,
+) -> Result<HashMap<String, RoleId>, SystemError> {
+    let mut role_ids: HashMap<String, RoleId> = guild
+        .roles
+        .values()
+        .map(|role| (role.name.clone(), role.id))
+        .collect();
+
+    for (role_name, role_config) in &server.roles {
+        match role_ids.get(role_name) {
+            Some(role_id) => {
+                update_role_if_needed(&guild.id, role_id, role_config, context).await?
+            }
+            None => {
+                let role = create_role(role_name, role_config, context, guild).await?;
+                role_ids.insert(role_name.clone(), role.id);
+            }
+        }
+    }
+
+    Ok(role_ids)
+}
+
+/// Creates a new role with the specified configuration.
+async fn create_role(
+    role_name: &str,
+    role_config: &RoleConfig,
+    context: &Context,
+    guild: &PartialGuild,
+) -> Result<Role, SystemError> {
+    let mut edit = CreateRole::new(role_name);
+
+    if let Some(color) = role_config.color {
+        edit = edit.colour(color);
+    }
+    if let Some(hoist) = role_config.hoist {
+        edit = edit.hoist(hoist);
+    }
+    if let Some(mentionable) = role_config.mentionable {
+        edit = edit.mentionable(mentionable);
+    }
+    if let Some(permissions) = role_config.permissions {
+        edit = edit.permissions(Permissions::from_bits_truncate(permissions));
+    }
+
+    let role = guild.create_role(&context.http, edit).await?;
+    Ok(role)
+}
+
+/// Updates the role if there are any changes in its configuration.
+async fn update_role_if_needed(
+    guild_id: &GuildId,
+    role_id: &RoleId,
+    role_config: &RoleConfig,
+    context: &Context,
 ) -> Result<(), SystemError> {
-    for (category_name, category_config) in categories {
-        let category_id =
-            find_or_create_category(category_name, category_config, context, channels, guild)
-                .await?;
-        process_channels(
-            context,
-            &category_config.channels,
-            channels,
-            guild,
-            &category_id,
-        )
-        .await?;
+    if role_config.color.is_some()
+        || role_config.hoist.is_some()
+        || role_config.mentionable.is_some()
+        || role_config.permissions.is_some()
+    {
+        let mut edit = EditRole::new();
+
+        if let Some(color) = role_config.color {
+            edit = edit.colour(color);
+        }
+        if let Some(hoist) = role_config.hoist {
+            edit = edit.hoist(hoist);
+        }
+        if let Some(mentionable) = role_config.mentionable {
+            edit = edit.mentionable(mentionable);
+        }
+        if let Some(permissions) = role_config.permissions {
+            edit = edit.permissions(Permissions::from_bits_truncate(permissions));
+        }
+
+        guild_id.edit_role(&context.http, *role_id, edit).await?;
     }
     Ok(())
 }
 
-/// Finds an existing category or creates a new one if it does not exist.
-async fn find_or_create_category(
+/// Resolves a `role name -> overwrite` map into serenity [`PermissionOverwrite`]s.
+fn build_overwrites(
+    permissions: &HashMap<String, PermissionOverwriteConfig>,
+    role_ids: &HashMap<String, RoleId>,
+) -> Result<Vec<PermissionOverwrite>, SystemError> {
+    permissions
+        .iter()
+        .map(|(role_name, overwrite)| {
+            let role_id = role_ids
+                .get(role_name)
+                .copied()
+                .ok_or_else(|| Error::UnknownRole(role_name.clone()))?;
+
+            Ok(PermissionOverwrite {
+                allow: Permissions::from_bits_truncate(overwrite.allow.unwrap_or(0)),
+                deny: Permissions::from_bits_truncate(overwrite.deny.unwrap_or(0)),
+                kind: PermissionOverwriteType::Role(role_id),
+            })
+        })
+        .collect()
+}
+
+/// Builds the set of configured category names and `(category, channel, kind)` keys, for use by the
+/// prune pass to tell which live channels have no counterpart in the config.
+fn config_keys(
+    categories: &HashMap<String, CategoryConfig>,
+) -> (HashSet<String>, HashSet<(String, String, ChannelType)>) {
+    let mut category_names = HashSet::new();
+    let mut channel_keys = HashSet::new();
+
+    for (category_name, category_config) in categories {
+        category_names.insert(category_name.clone());
+        for (channel_name, channel_config) in &category_config.channels {
+            channel_keys.insert((
+                category_name.clone(),
+                channel_name.clone(),
+                channel_type(channel_config.kind),
+            ));
+        }
+    }
+
+    (category_names, channel_keys)
+}
+
+/// Walks the config and the live channel map and computes the full list of changes between them.
+fn diff(server: &ServerConfig, channels: &HashMap<ChannelId, GuildChannel>) -> Vec<Change> {
+    let mut changes = Vec::new();
+
+    for (category_name, category_config) in &server.categories {
+        diff_category(category_name, category_config, channels, &mut changes);
+    }
+
+    if server.prune {
+        let (category_names, channel_keys) = config_keys(&server.categories);
+        changes.extend(diff_prune(channels, &category_names, &channel_keys));
+    }
+
+    changes
+}
+
+/// Diffs a single category, and the channels configured under it, against the live channels.
+fn diff_category(
     category_name: &str,
     category_config: &CategoryConfig,
-    context: &Context,
+    channels: &HashMap<ChannelId, GuildChannel>,
+    changes: &mut Vec<Change>,
+) {
+    let existing = channels
+        .values()
+        .find(|c| c.kind == ChannelType::Category && c.name == category_name);
 
-    channels: &mut HashMap<ChannelId, GuildChannel>,
-    guild: &PartialGuild,
-) -> Result<ChannelId, SystemError> {
-    if let Some(category_channel) = channels
+    let category_id = match existing {
+        Some(category) => {
+            let mut fields = Vec::new();
+
+            if category_config.description.is_some() {
+                fields.push("description".to_owned());
+            }
+            if category_config.nsfw.is_some() {
+                fields.push("nsfw".to_owned());
+            }
+            if !category_config.permissions.is_empty() {
+                fields.push("permissions".to_owned());
+            }
+
+            if !fields.is_empty() {
+                changes.push(Change::UpdateCategory {
+                    name: category_name.to_owned(),
+                    fields,
+                });
+            }
+
+            Some(category.id)
+        }
+        None => {
+            changes.push(Change::CreateCategory(category_name.to_owned()));
+            None
+        }
+    };
+
+    for (channel_name, channel_config) in &category_config.channels {
+        diff_channel(
+            category_name,
+            channel_name,
+            channel_config,
+            &category_config.permissions,
+            category_id,
+            channels,
+            changes,
+        );
+    }
+}
+
+/// Diffs a single channel, scoped to its (possibly not-yet-existing) parent category, against
+/// the live channels.
+fn diff_channel(
+    category_name: &str,
+    channel_name: &str,
+    channel_config: &ChannelConfig,
+    category_permissions: &HashMap<String, PermissionOverwriteConfig>,
+    category_id: Option<ChannelId>,
+    channels: &HashMap<ChannelId, GuildChannel>,
+    changes: &mut Vec<Change>,
+) {
+    let effective_permissions = if channel_config.permissions.is_empty() {
+        category_permissions
+    } else {
+        &channel_config.permissions
+    };
+    let existing = category_id.and_then(|category_id| {
+        channels.values().find(|c| {
+            c.kind == channel_type(channel_config.kind)
+                && c.name == channel_name
+                && c.parent_id == Some(category_id)
+        })
+    });
+
+    match existing {
+        Some(_) => {
+            let mut fields = Vec::new();
+
+            if channel_config.topic.is_some() {
+                fields.push("topic".to_owned());
+            }
+            if channel_config.nsfw.is_some() {
+                fields.push("nsfw".to_owned());
+            }
+            if channel_config.position.is_some() {
+                fields.push("position".to_owned());
+            }
+            if channel_config.kind == ChannelKind::Voice {
+                if channel_config.bitrate.is_some() {
+                    fields.push("bitrate".to_owned());
+                }
+                if channel_config.user_limit.is_some() {
+                    fields.push("user_limit".to_owned());
+                }
+            }
+            if !effective_permissions.is_empty() {
+                fields.push("permissions".to_owned());
+            }
+
+            if !fields.is_empty() {
+                changes.push(Change::UpdateChannel {
+                    category: category_name.to_owned(),
+                    name: channel_name.to_owned(),
+                    fields,
+                });
+            }
+        }
+        None => changes.push(Change::CreateChannel {
+            category: category_name.to_owned(),
+            name: channel_name.to_owned(),
+        }),
+    }
+}
+
+/// Whether a live, non-category channel has no counterpart in the config and should be pruned.
+///
+/// Top-level channels (`parent_id` is `None`) have no representation in the config format, so
+/// they can never be "in config" — they're excluded here rather than being treated as missing
+/// and deleted the moment `prune` is turned on.
+fn channel_is_pruneable(
+    parent_id: Option<ChannelId>,
+    kind: ChannelType,
+    name: &str,
+    category_name_by_id: &HashMap<ChannelId, &str>,
+    channel_keys: &HashSet<(String, String, ChannelType)>,
+) -> bool {
+    let Some(parent_id) = parent_id else {
+        return false;
+    };
+
+    let in_config = category_name_by_id
+        .get(&parent_id)
+        .is_some_and(|category_name| {
+            channel_keys.contains(&(category_name.to_string(), name.to_string(), kind))
+        });
+
+    !in_config
+}
+
+/// Computes the prospective deletions for every live category, or channel within a category,
+/// that has no counterpart in the config, without touching the guild. Top-level channels are
+/// never listed.
+fn diff_prune(
+    channels: &HashMap<ChannelId, GuildChannel>,
+    category_names: &HashSet<String>,
+    channel_keys: &HashSet<(String, String, ChannelType)>,
+) -> Vec<Change> {
+    let category_name_by_id: HashMap<ChannelId, &str> = channels
         .values()
-        .find(|c| c.kind == ChannelType::Category && c.name == category_name)
+        .filter(|c| c.kind == ChannelType::Category)
+        .map(|c| (c.id, c.name.as_str()))
+        .collect();
+
+    let mut changes = Vec::new();
+
+    for channel in channels
+        .values()
+        .filter(|c| c.kind != ChannelType::Category)
     {
-        update_category_if_needed(&category_channel.id, category_config, context).await?;
-        Ok(category_channel.id)
-    } else {
-        let mut edit = CreateChannel::new(category_name);
-        edit = edit.kind(ChannelType::Category);
+        if channel_is_pruneable(
+            channel.parent_id,
+            channel.kind,
+            &channel.name,
+            &category_name_by_id,
+            channel_keys,
+        ) {
+            changes.push(Change::Prune(format!("channel '{}'", channel.name)));
+        }
+    }
 
-        if let Some(description) = &category_config.description {
-            edit = edit.topic(description);
+    for category in channels
+        .values()
+        .filter(|c| c.kind == ChannelType::Category)
+    {
+        if !category_names.contains(&category.name) {
+            changes.push(Change::Prune(format!("category '{}'", category.name)));
         }
+    }
+
+    changes
+}
+
+/// Applies a previously computed change list to the guild.
+///
+/// Per-item failures are recorded on the returned report rather than aborting the whole run.
+async fn apply_changes(
+    changes: &[Change],
+    server: &ServerConfig,
+    context: &Context,
+    guild: &mut PartialGuild,
+    channels: &HashMap<ChannelId, GuildChannel>,
+    role_ids: &HashMap<String, RoleId>,
+) -> ApplyReport {
+    let mut category_ids: HashMap<String, ChannelId> = channels
+        .values()
+        .filter(|c| c.kind == ChannelType::Category)
+        .map(|c| (c.name.clone(), c.id))
+        .collect();
 
-        if let Some(nsfw) = category_config.nsfw {
-            edit = edit.nsfw(nsfw);
+    let mut report = ApplyReport::default();
+
+    for change in changes {
+        match change {
+            Change::CreateCategory(name) => {
+                let category_config = &server.categories[name];
+                match create_category(name, category_config, context, guild, role_ids).await {
+                    Ok(id) => {
+                        category_ids.insert(name.clone(), id);
+                        report.categories_created += 1;
+                    }
+                    Err(err) => report.failures.push(format!("category '{name}': {err}")),
+                }
+            }
+            Change::UpdateCategory { name, .. } => {
+                let category_config = &server.categories[name];
+                let category_id = category_ids[name];
+                match update_category_if_needed(&category_id, category_config, context, role_ids)
+                    .await
+                {
+                    Ok(()) => report.categories_updated += 1,
+                    Err(err) => report.failures.push(format!("category '{name}': {err}")),
+                }
+            }
+            Change::CreateChannel { category, name } => {
+                let Some(&category_id) = category_ids.get(category) else {
+                    report.failures.push(format!(
+                        "channel '{category}/{name}': parent category failed to create"
+                    ));
+                    continue;
+                };
+                let category_config = &server.categories[category];
+                let channel_config = &category_config.channels[name];
+                let permissions = effective_permissions(channel_config, category_config);
+                match create_channel(
+                    name,
+                    channel_config,
+                    permissions,
+                    context,
+                    guild,
+                    &category_id,
+                    role_ids,
+                )
+                .await
+                {
+                    Ok(_) => report.channels_created += 1,
+                    Err(err) => report
+                        .failures
+                        .push(format!("channel '{category}/{name}': {err}")),
+                }
+            }
+            Change::UpdateChannel { category, name, .. } => {
+                let Some(&category_id) = category_ids.get(category) else {
+                    report.failures.push(format!(
+                        "channel '{category}/{name}': parent category failed to create"
+                    ));
+                    continue;
+                };
+                let category_config = &server.categories[category];
+                let channel_config = &category_config.channels[name];
+                let permissions = effective_permissions(channel_config, category_config);
+                let channel = channels.values().find(|c| {
+                    c.kind == channel_type(channel_config.kind)
+                        && &c.name == name
+                        && c.parent_id == Some(category_id)
+                });
+
+                match channel {
+                    Some(channel) => {
+                        match update_channel_if_needed(
+                            &channel.id,
+                            channel_config,
+                            permissions,
+                            context,
+                            role_ids,
+                        )
+                        .await
+                        {
+                            Ok(()) => report.channels_updated += 1,
+                            Err(err) => report
+                                .failures
+                                .push(format!("channel '{category}/{name}': {err}")),
+                        }
+                    }
+                    None => report
+                        .failures
+                        .push(format!("channel '{category}/{name}': not found")),
+                }
+            }
+            Change::Prune(_) => {}
         }
+    }
+
+    report
+}
 
-        let new_category = guild.create_channel(&context.http, edit).await?;
-        Ok(new_category.id)
+/// Returns the permission overwrites that should apply to a channel: its own if it defines any,
+/// otherwise its category's, mirroring Discord's "Sync to category" behavior.
+fn effective_permissions<'a>(
+    channel_config: &'a ChannelConfig,
+    category_config: &'a CategoryConfig,
+) -> &'a HashMap<String, PermissionOverwriteConfig> {
+    if channel_config.permissions.is_empty() {
+        &category_config.permissions
+    } else {
+        &channel_config.permissions
     }
 }
 
+/// Creates a new category with the specified configuration.
+async fn create_category(
+    category_name: &str,
+    category_config: &CategoryConfig,
+    context: &Context,
+    guild: &PartialGuild,
+    role_ids: &HashMap<String, RoleId>,
+) -> Result<ChannelId, SystemError> {
+    let mut edit = CreateChannel::new(category_name);
+    edit = edit.kind(ChannelType::Category);
+
+    if let Some(description) = &category_config.description {
+        edit = edit.topic(description);
+    }
+
+    if let Some(nsfw) = category_config.nsfw {
+        edit = edit.nsfw(nsfw);
+    }
+
+    if !category_config.permissions.is_empty() {
+        edit = edit.permissions(build_overwrites(&category_config.permissions, role_ids)?);
+    }
+
+    let new_category = guild.create_channel(&context.http, edit).await?;
+    Ok(new_category.id)
+}
+
 /// Updates the category if there are any changes in its configuration.
 async fn update_category_if_needed(
     category_id: &ChannelId,
     category_config: &CategoryConfig,
     context: &Context,
-) -> Result<(), Error> {
-    if category_config.description.is_some() || category_config.nsfw.is_some() {
+    role_ids: &HashMap<String, RoleId>,
+) -> Result<(), SystemError> {
+    if category_config.description.is_some()
+        || category_config.nsfw.is_some()
+        || !category_config.permissions.is_empty()
+    {
         let mut edit = EditChannel::default();
 
         if let Some(description) = &category_config.description {
@@ -144,48 +857,11 @@ async fn update_category_if_needed(
             edit = edit.nsfw(*nsfw);
         }
 
-        category_id.edit(&context.http, edit).await?;
-    }
-    Ok(())
-}
-
-/// Processes channels within a category by creating or updating them.
-async fn process_channels(
-    context: &Context,
-    config: &HashMap<String, ChannelConfig>,
-    channels: &mut HashMap<ChannelId, GuildChannel>,
-    guild: &mut PartialGuild,
-    category_id: &ChannelId,
-) -> Result<(), SystemError> {
-    for (channel_name, channel_config) in config {
-        find_or_create_channel(
-            channel_name,
-            channel_config,
-            context,
-            channels,
-            guild,
-            category_id,
-        )
-        .await?;
-    }
-    Ok(())
-}
+        if !category_config.permissions.is_empty() {
+            edit = edit.permissions(build_overwrites(&category_config.permissions, role_ids)?);
+        }
 
-/// Finds an existing channel or creates a new one if it does not exist.
-async fn find_or_create_channel(
-    channel_name: &str,
-    channel_config: &ChannelConfig,
-    context: &Context,
-    channels: &mut HashMap<ChannelId, GuildChannel>,
-    guild: &mut PartialGuild,
-    category_id: &ChannelId,
-) -> Result<(), SystemError> {
-    if let Some(channel) = channels.values().find(|c| {
-        c.kind == ChannelType::Text && c.name == channel_name && c.parent_id == Some(*category_id)
-    }) {
-        update_channel_if_needed(&channel.id, channel_config, context).await?;
-    } else {
-        create_channel(channel_name, channel_config, context, guild, category_id).await?;
+        category_id.edit(&context.http, edit).await?;
     }
     Ok(())
 }
@@ -194,7 +870,9 @@ async fn find_or_create_channel(
 async fn update_channel_if_needed(
     channel_id: &ChannelId,
     channel_config: &ChannelConfig,
+    permissions: &HashMap<String, PermissionOverwriteConfig>,
     context: &Context,
+    role_ids: &HashMap<String, RoleId>,
 ) -> Result<(), SystemError> {
     let mut edit = EditChannel::new();
 
@@ -212,6 +890,19 @@ async fn update_channel_if_needed(
         edit = edit.position(position as u16);
     }
 
+    if channel_config.kind == ChannelKind::Voice {
+        if let Some(bitrate) = channel_config.bitrate {
+            edit = edit.bitrate(bitrate);
+        }
+        if let Some(user_limit) = channel_config.user_limit {
+            edit = edit.user_limit(user_limit);
+        }
+    }
+
+    if !permissions.is_empty() {
+        edit = edit.permissions(build_overwrites(permissions, role_ids)?);
+    }
+
     channel_id.edit(&context.http, edit).await?;
     Ok(())
 }
@@ -220,13 +911,15 @@ async fn update_channel_if_needed(
 async fn create_channel(
     channel_name: &str,
     channel_config: &ChannelConfig,
+    permissions: &HashMap<String, PermissionOverwriteConfig>,
     context: &Context,
     guild: &mut PartialGuild,
     category_id: &ChannelId,
+    role_ids: &HashMap<String, RoleId>,
 ) -> Result<ChannelId, SystemError> {
     let mut edit = CreateChannel::new(channel_name);
 
-    edit = edit.kind(ChannelType::Text);
+    edit = edit.kind(channel_type(channel_config.kind));
     edit = edit.category(*category_id);
 
     if let Some(topic) = &channel_config.topic {
@@ -239,6 +932,149 @@ async fn create_channel(
         edit = edit.position(position as u16);
     }
 
+    if channel_config.kind == ChannelKind::Voice {
+        if let Some(bitrate) = channel_config.bitrate {
+            edit = edit.bitrate(bitrate);
+        }
+        if let Some(user_limit) = channel_config.user_limit {
+            edit = edit.user_limit(user_limit);
+        }
+    }
+
+    if !permissions.is_empty() {
+        edit = edit.permissions(build_overwrites(permissions, role_ids)?);
+    }
+
     let new_channel = guild.create_channel(&context.http, edit).await?;
     Ok(new_channel.id)
 }
+
+/// Deletes any live category, or channel within a category, that has no counterpart in the
+/// config. Top-level channels are never deleted.
+///
+/// Child channels are deleted before their parent category so a category is never left orphaned
+/// with channels that no longer exist in the config but haven't been removed from Discord yet.
+/// Returns the number of successful deletions and any per-deletion failures; a failure to delete
+/// one item doesn't stop the rest from being pruned.
+async fn prune_channels(
+    context: &Context,
+    channels: &HashMap<ChannelId, GuildChannel>,
+    category_names: &HashSet<String>,
+    channel_keys: &HashSet<(String, String, ChannelType)>,
+) -> (usize, Vec<String>) {
+    let category_name_by_id: HashMap<ChannelId, &str> = channels
+        .values()
+        .filter(|c| c.kind == ChannelType::Category)
+        .map(|c| (c.id, c.name.as_str()))
+        .collect();
+
+    let mut pruned = 0;
+    let mut failures = Vec::new();
+
+    for channel in channels
+        .values()
+        .filter(|c| c.kind != ChannelType::Category)
+    {
+        let should_prune = channel_is_pruneable(
+            channel.parent_id,
+            channel.kind,
+            &channel.name,
+            &category_name_by_id,
+            channel_keys,
+        );
+
+        if should_prune {
+            match channel.id.delete(&context.http).await {
+                Ok(_) => pruned += 1,
+                Err(err) => failures.push(format!("channel '{}': {}", channel.name, err)),
+            }
+        }
+    }
+
+    for category in channels
+        .values()
+        .filter(|c| c.kind == ChannelType::Category)
+    {
+        if !category_names.contains(&category.name) {
+            match category.id.delete(&context.http).await {
+                Ok(_) => pruned += 1,
+                Err(err) => failures.push(format!("category '{}': {}", category.name, err)),
+            }
+        }
+    }
+
+    (pruned, failures)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn channel_keys(entries: &[(&str, &str, ChannelType)]) -> HashSet<(String, String, ChannelType)> {
+        entries
+            .iter()
+            .map(|(category, name, kind)| (category.to_string(), name.to_string(), *kind))
+            .collect()
+    }
+
+    #[test]
+    fn top_level_channels_are_never_pruned() {
+        // No config has a key for it, but with no parent category it still must not be pruned —
+        // the config format simply can't represent it either way.
+        let category_name_by_id = HashMap::new();
+        let channel_keys = HashSet::new();
+
+        assert!(!channel_is_pruneable(
+            None,
+            ChannelType::Text,
+            "general",
+            &category_name_by_id,
+            &channel_keys,
+        ));
+    }
+
+    #[test]
+    fn categorized_channel_missing_from_config_is_pruned() {
+        let category_id = ChannelId::new(1);
+        let category_name_by_id = HashMap::from([(category_id, "info")]);
+        let channel_keys = channel_keys(&[("info", "rules", ChannelType::Text)]);
+
+        assert!(channel_is_pruneable(
+            Some(category_id),
+            ChannelType::Text,
+            "announcements",
+            &category_name_by_id,
+            &channel_keys,
+        ));
+    }
+
+    #[test]
+    fn categorized_channel_present_in_config_is_kept() {
+        let category_id = ChannelId::new(1);
+        let category_name_by_id = HashMap::from([(category_id, "info")]);
+        let channel_keys = channel_keys(&[("info", "rules", ChannelType::Text)]);
+
+        assert!(!channel_is_pruneable(
+            Some(category_id),
+            ChannelType::Text,
+            "rules",
+            &category_name_by_id,
+            &channel_keys,
+        ));
+    }
+
+    #[test]
+    fn channel_with_mismatched_kind_is_pruned() {
+        let category_id = ChannelId::new(1);
+        let category_name_by_id = HashMap::from([(category_id, "info")]);
+        let channel_keys = channel_keys(&[("info", "chat", ChannelType::Voice)]);
+
+        assert!(channel_is_pruneable(
+            Some(category_id),
+            ChannelType::Text,
+            "chat",
+            &category_name_by_id,
+            &channel_keys,
+        ));
+    }
+}
@@ -0,0 +1,3 @@
+//! Actions that mutate or inspect a Discord guild on behalf of a command.
+
+pub mod config;
@@ -0,0 +1,78 @@
+//! Helpers for delivering long text reports to Discord within its message-size limits.
+
+/// Discord caps message content at 2000 characters; leave headroom for the code-fence wrapper.
+const MAX_CARD_BODY_LEN: usize = 1900;
+
+/// Splits a report into Discord-sized "cards": code-block-wrapped chunks that together contain
+/// the whole report, without truncating any line, for cases where a single 2000-character
+/// message isn't enough to deliver it.
+pub fn split_into_cards(report: &str) -> Vec<String> {
+    split_into_fenced_cards(report, "")
+}
+
+/// Like [`split_into_cards`], but fences each card as a `yaml` code block for syntax highlighting.
+pub fn split_into_yaml_cards(yaml: &str) -> Vec<String> {
+    split_into_fenced_cards(yaml, "yaml")
+}
+
+/// Splits `report` into fenced cards, using `lang` as the code block's language tag.
+///
+/// Lines longer than [`MAX_CARD_BODY_LEN`] on their own are split further, so no card can ever
+/// exceed Discord's message-size limit regardless of how the report is laid out.
+fn split_into_fenced_cards(report: &str, lang: &str) -> Vec<String> {
+    let mut cards = Vec::new();
+    let mut current = String::new();
+
+    for line in report.lines() {
+        for piece in split_long_line(line) {
+            if !current.is_empty() && current.len() + piece.len() + 1 > MAX_CARD_BODY_LEN {
+                cards.push(wrap_card(&current, lang));
+                current.clear();
+            }
+
+            if !current.is_empty() {
+                current.push('\n');
+            }
+            current.push_str(piece);
+        }
+    }
+
+    if !current.is_empty() {
+        cards.push(wrap_card(&current, lang));
+    }
+
+    if cards.is_empty() {
+        cards.push(wrap_card("", lang));
+    }
+
+    cards
+}
+
+/// Splits a single line into chunks of at most [`MAX_CARD_BODY_LEN`] bytes, on char boundaries.
+fn split_long_line(line: &str) -> Vec<&str> {
+    if line.len() <= MAX_CARD_BODY_LEN {
+        return vec![line];
+    }
+
+    let mut pieces = Vec::new();
+    let mut start = 0;
+    let mut len = 0;
+
+    for (i, ch) in line.char_indices() {
+        let ch_len = ch.len_utf8();
+        if len + ch_len > MAX_CARD_BODY_LEN {
+            pieces.push(&line[start..i]);
+            start = i;
+            len = 0;
+        }
+        len += ch_len;
+    }
+    pieces.push(&line[start..]);
+
+    pieces
+}
+
+/// Wraps a single card's body in a fenced code block.
+fn wrap_card(body: &str, lang: &str) -> String {
+    format!("```{lang}\n{body}\n```")
+}
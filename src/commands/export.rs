@@ -0,0 +1,70 @@
+use crate::{actions, api, CommandError, Context, Handler};
+use serenity::all::{
+    CommandInteraction, CreateInteractionResponseFollowup, EditInteractionResponse, GuildId,
+    Message,
+};
+
+/// Introspects the current guild and replies with a YAML config that reproduces it when fed
+/// back into `~set`.
+pub async fn export(
+    _: &Handler,
+    context: &Context,
+    message: &Message,
+    _content: String,
+) -> Result<(), CommandError> {
+    let guild_id = message
+        .guild_id
+        .ok_or_else(|| CommandError::User("Cannot run this outside of a Guild.".into()))?;
+
+    let yaml = render_export(context, guild_id).await?;
+
+    for card in api::split_into_yaml_cards(&yaml) {
+        message.channel_id.say(&context.http, card).await?;
+    }
+
+    Ok(())
+}
+
+/// The `/export` slash command equivalent of [`export`], reporting the config as an ephemeral reply.
+pub async fn export_interaction(
+    _: &Handler,
+    context: &Context,
+    command: &CommandInteraction,
+    _content: String,
+) -> Result<(), CommandError> {
+    let guild_id = command
+        .guild_id
+        .ok_or_else(|| CommandError::User("Cannot run this outside of a Guild.".into()))?;
+
+    command.defer_ephemeral(&context.http).await?;
+
+    let yaml = render_export(context, guild_id).await?;
+    let mut cards = api::split_into_yaml_cards(&yaml).into_iter();
+
+    if let Some(first) = cards.next() {
+        command
+            .edit_response(&context.http, EditInteractionResponse::new().content(first))
+            .await?;
+    }
+
+    for card in cards {
+        command
+            .create_followup(
+                &context.http,
+                CreateInteractionResponseFollowup::new()
+                    .content(card)
+                    .ephemeral(true),
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Fetches the guild's current config and renders it as YAML.
+async fn render_export(context: &Context, guild_id: GuildId) -> Result<String, CommandError> {
+    let config = actions::config::export(context, guild_id).await?;
+
+    serde_yaml::to_string(&config)
+        .map_err(|_| CommandError::User("Failed to serialize the server config.".to_string()))
+}
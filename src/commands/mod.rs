@@ -0,0 +1,31 @@
+//! Chat commands understood by the bot, reachable from both prefixed messages and slash commands.
+
+mod export;
+mod plan;
+mod set;
+
+pub use export::{export, export_interaction};
+pub use plan::{plan, plan_interaction};
+pub use set::{set, set_interaction};
+
+use crate::settings::Config;
+use crate::CommandError;
+use regex::Regex;
+
+/// Extracts and parses the YAML config out of a command's raw content.
+///
+/// Accepts the content fenced in a ` ```yaml ` block, as used by the prefixed message commands,
+/// or raw YAML, as passed through a slash command option.
+pub(crate) fn parse_config(content: &str) -> Result<Config, CommandError> {
+    let re = Regex::new(r"```yaml\n([\s\S]*?)\n```").unwrap();
+
+    let yaml_content = re
+        .captures(content)
+        .and_then(|capture| capture.get(1))
+        .map(|x| x.as_str())
+        .unwrap_or(content);
+
+    serde_yaml::from_str(yaml_content).map_err(|_| {
+        CommandError::User("Invalid YAML structure for configuring the server.".to_string())
+    })
+}
@@ -0,0 +1,80 @@
+use crate::commands::parse_config;
+use crate::{actions, api, CommandError, Context, Handler};
+use serenity::all::{
+    CommandInteraction, CreateInteractionResponseFollowup, EditInteractionResponse, Message,
+};
+
+/// Computes and reports the changes `~set` would make for the given config, without applying them.
+pub async fn plan(
+    handler: &Handler,
+    context: &Context,
+    message: &Message,
+    content: String,
+) -> Result<(), CommandError> {
+    let guild_id = message
+        .guild_id
+        .ok_or_else(|| CommandError::User("Cannot run this outside of a Guild.".into()))?;
+
+    let config = parse_config(&content)?;
+
+    let changes = actions::config::plan(handler, &config, context, guild_id).await?;
+    let report = render_plan(&changes);
+
+    for card in api::split_into_cards(&report) {
+        message.channel_id.say(&context.http, card).await?;
+    }
+
+    Ok(())
+}
+
+/// The `/plan` slash command equivalent of [`plan`], reporting the diff as an ephemeral reply.
+pub async fn plan_interaction(
+    handler: &Handler,
+    context: &Context,
+    command: &CommandInteraction,
+    content: String,
+) -> Result<(), CommandError> {
+    let guild_id = command
+        .guild_id
+        .ok_or_else(|| CommandError::User("Cannot run this outside of a Guild.".into()))?;
+
+    let config = parse_config(&content)?;
+
+    command.defer_ephemeral(&context.http).await?;
+
+    let changes = actions::config::plan(handler, &config, context, guild_id).await?;
+    let report = render_plan(&changes);
+    let mut cards = api::split_into_cards(&report).into_iter();
+
+    if let Some(first) = cards.next() {
+        command
+            .edit_response(&context.http, EditInteractionResponse::new().content(first))
+            .await?;
+    }
+
+    for card in cards {
+        command
+            .create_followup(
+                &context.http,
+                CreateInteractionResponseFollowup::new()
+                    .content(card)
+                    .ephemeral(true),
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Renders a computed change list as a plain-text report for a plan reply.
+fn render_plan(changes: &[actions::config::Change]) -> String {
+    if changes.is_empty() {
+        "No changes. The server already matches the config.".to_string()
+    } else {
+        changes
+            .iter()
+            .map(|change| change.to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
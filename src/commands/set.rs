@@ -1,11 +1,16 @@
-use crate::settings::Config;
-use crate::{actions, CommandError, Context, Handler};
-use regex::Regex;
-use serde_yaml;
-use serenity::all::Message;
+use crate::actions::config::ApplyReport;
+use crate::commands::parse_config;
+use crate::{actions, api, CommandError, Context, Handler};
+use serenity::all::{
+    ChannelId, Colour, CommandInteraction, CreateEmbed, CreateInteractionResponseFollowup,
+    CreateMessage, EditInteractionResponse, Message,
+};
+
+const SUCCESS_COLOR: Colour = Colour::new(0x43_b5_81);
+const FAILURE_COLOR: Colour = Colour::new(0xf0_47_47);
 
 pub async fn set(
-    _: &Handler,
+    handler: &Handler,
     context: &Context,
     message: &Message,
     content: String,
@@ -14,30 +19,15 @@ pub async fn set(
         .guild_id
         .ok_or_else(|| CommandError::User("Cannot run this outside of a Guild.".into()))?;
 
-    let re = Regex::new(r"```yaml\n([\s\S]*?)\n```").unwrap();
-
-    let yaml_content = re
-        .captures(&content)
-        .and_then(|capture| capture.get(1))
-        .map(|x| x.as_str())
-        .unwrap_or_else(|| &content);
-
-    let config: Config = serde_yaml::from_str(yaml_content).map_err(|_| {
-        CommandError::User("Invalid YAML structure for configuring the server.".to_string())
-    })?;
+    let config = parse_config(&content)?;
 
     message
         .channel_id
         .say(&context.http, "Configuring...".to_string())
         .await?;
 
-    match actions::config::run(config, context.clone(), guild_id).await {
-        Ok(_) => {
-            message
-                .channel_id
-                .say(&context.http, "Finished...".to_string())
-                .await?;
-        }
+    match actions::config::run(handler, config, context.clone(), guild_id).await {
+        Ok(report) => send_report(context, message.channel_id, &report).await?,
         Err(err) => {
             eprint!("Failed at configuring");
             message
@@ -52,3 +42,117 @@ pub async fn set(
 
     Ok(())
 }
+
+/// The `/set` slash command equivalent of [`set`], reporting the outcome as an ephemeral reply.
+pub async fn set_interaction(
+    handler: &Handler,
+    context: &Context,
+    command: &CommandInteraction,
+    content: String,
+) -> Result<(), CommandError> {
+    let guild_id = command
+        .guild_id
+        .ok_or_else(|| CommandError::User("Cannot run this outside of a Guild.".into()))?;
+
+    let config = parse_config(&content)?;
+
+    command.defer_ephemeral(&context.http).await?;
+
+    match actions::config::run(handler, config, context.clone(), guild_id).await {
+        Ok(report) => {
+            command
+                .edit_response(
+                    &context.http,
+                    EditInteractionResponse::new().embed(build_embed(&report)),
+                )
+                .await?;
+
+            if !report.failures.is_empty() {
+                for card in api::split_into_cards(&report.failures.join("\n")) {
+                    command
+                        .create_followup(
+                            &context.http,
+                            CreateInteractionResponseFollowup::new()
+                                .content(card)
+                                .ephemeral(true),
+                        )
+                        .await?;
+                }
+            }
+        }
+        Err(err) => {
+            eprint!("Failed at configuring");
+            command
+                .edit_response(
+                    &context.http,
+                    EditInteractionResponse::new()
+                        .content(format!("Could not complete the setup. {:}", err)),
+                )
+                .await?;
+        }
+    };
+
+    Ok(())
+}
+
+/// Sends the embed summary for a completed run, followed by per-item failure cards if needed.
+async fn send_report(
+    context: &Context,
+    channel_id: ChannelId,
+    report: &ApplyReport,
+) -> Result<(), CommandError> {
+    channel_id
+        .send_message(&context.http, CreateMessage::new().embed(build_embed(report)))
+        .await?;
+
+    if !report.failures.is_empty() {
+        for card in api::split_into_cards(&report.failures.join("\n")) {
+            channel_id.say(&context.http, card).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Builds the summary embed for a completed run, color-coded by whether anything failed.
+fn build_embed(report: &ApplyReport) -> CreateEmbed {
+    let color = if report.failures.is_empty() {
+        SUCCESS_COLOR
+    } else {
+        FAILURE_COLOR
+    };
+
+    let mut embed = CreateEmbed::new()
+        .title("Configuration applied")
+        .color(color)
+        .field(
+            "Categories",
+            format!(
+                "{} created, {} updated",
+                report.categories_created, report.categories_updated
+            ),
+            true,
+        )
+        .field(
+            "Channels",
+            format!(
+                "{} created, {} updated",
+                report.channels_created, report.channels_updated
+            ),
+            true,
+        );
+
+    if report.pruned > 0 {
+        embed = embed.field("Pruned", report.pruned.to_string(), true);
+    }
+
+    if !report.failures.is_empty() {
+        embed = embed.field(
+            "Failures",
+            format!("{} item(s) failed — see below", report.failures.len()),
+            false,
+        );
+    }
+
+    embed
+}
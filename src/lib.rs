@@ -1,13 +1,18 @@
 //! Discord bot for configuration as code.
 
 use error::CommandError;
-use serenity::all::{Context, EventHandler, GuildId, Message, Ready};
+use serenity::all::{
+    ChannelId, Command, CommandInteraction, CommandOptionType, Context, CreateCommand,
+    CreateCommandOption, CreateInteractionResponse, CreateInteractionResponseMessage,
+    EventHandler, GuildChannel, GuildId, Interaction, Message, Ready,
+};
 use serenity::async_trait;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
 pub mod actions;
+pub mod api;
 pub mod commands;
 pub mod error;
 pub mod settings;
@@ -16,7 +21,16 @@ pub mod settings;
 #[derive(Default, Clone)]
 pub struct Handler {
     pub cooldown: Arc<RwLock<HashMap<GuildId, usize>>>,
+    /// A per-guild fallback cache of categories/channels, used when serenity's own gateway
+    /// cache hasn't (yet) got a guild, so a `~set`/`~plan` run doesn't always pay for a full
+    /// HTTP channel list fetch.
+    pub channel_cache: Arc<RwLock<HashMap<GuildId, HashMap<ChannelId, GuildChannel>>>>,
     pub prefix: &'static str,
+    /// Whether to handle prefix commands (`~set`, `~plan`, `~export`) parsed from message
+    /// content. Slash commands work regardless of this flag; leave it `false` if the bot only
+    /// needs slash commands, so the client doesn't have to request the privileged
+    /// `MESSAGE_CONTENT` intent.
+    pub message_commands: bool,
 }
 
 /// Parses the message content and executes the corresponding command if found.
@@ -25,13 +39,31 @@ pub async fn parse_commands(
     context: Context,
     message: Message,
 ) -> Result<(), error::Error> {
+    if !handler.message_commands {
+        return Ok(());
+    }
+
     if let Some(content) = message.content.clone().strip_prefix(handler.prefix) {
-        if let Some((command_name, content)) = content.split_once(check_whitespace) {
-            if command_name == "set" {
-                let res = commands::set(handler, &context, &message, content.to_owned()).await;
+        let (command_name, content) = content
+            .split_once(check_whitespace)
+            .unwrap_or((content.trim_end(), ""));
 
-                return execute_command(res, message, context).await;
-            }
+        if command_name == "set" {
+            let res = commands::set(handler, &context, &message, content.to_owned()).await;
+
+            return execute_command(res, message, context).await;
+        }
+
+        if command_name == "plan" {
+            let res = commands::plan(handler, &context, &message, content.to_owned()).await;
+
+            return execute_command(res, message, context).await;
+        }
+
+        if command_name == "export" {
+            let res = commands::export(handler, &context, &message, content.to_owned()).await;
+
+            return execute_command(res, message, context).await;
         }
     }
     Ok(())
@@ -56,11 +88,93 @@ async fn execute_command<T>(
     Ok(())
 }
 
+/// Parses a slash command interaction and executes the corresponding command if found.
+pub async fn parse_interaction(
+    handler: &Handler,
+    context: Context,
+    interaction: Interaction,
+) -> Result<(), error::Error> {
+    let Interaction::Command(command) = interaction else {
+        return Ok(());
+    };
+
+    let content = command
+        .data
+        .options
+        .iter()
+        .find(|option| option.name == "config")
+        .and_then(|option| option.value.as_str())
+        .unwrap_or_default()
+        .to_owned();
+
+    match command.data.name.as_str() {
+        "set" => {
+            let res = commands::set_interaction(handler, &context, &command, content).await;
+            execute_interaction_command(res, command, context).await
+        }
+        "plan" => {
+            let res = commands::plan_interaction(handler, &context, &command, content).await;
+            execute_interaction_command(res, command, context).await
+        }
+        "export" => {
+            let res = commands::export_interaction(handler, &context, &command, content).await;
+            execute_interaction_command(res, command, context).await
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Executes the given interaction command and handles any errors that occur.
+async fn execute_interaction_command<T>(
+    err: Result<T, CommandError>,
+    command: CommandInteraction,
+    context: Context,
+) -> Result<(), error::Error> {
+    if let Err(err) = err {
+        match err {
+            CommandError::User(error_message) => {
+                let response = CreateInteractionResponseMessage::new()
+                    .content(error_message)
+                    .ephemeral(true);
+
+                command
+                    .create_response(&context.http, CreateInteractionResponse::Message(response))
+                    .await?;
+            }
+            CommandError::System(system_error) => {
+                return Err(system_error);
+            }
+        }
+    }
+    Ok(())
+}
+
 /// Checks for whitespace or newline characters.
 fn check_whitespace(x: char) -> bool {
     char::is_whitespace(x) || x == '\n'
 }
 
+/// Builds the global `/set`, `/plan`, and `/export` slash commands.
+fn global_commands() -> Vec<CreateCommand> {
+    let config_option = CreateCommandOption::new(
+        CommandOptionType::String,
+        "config",
+        "The YAML server config to apply.",
+    )
+    .required(true);
+
+    vec![
+        CreateCommand::new("set")
+            .description("Apply a YAML server config.")
+            .add_option(config_option.clone()),
+        CreateCommand::new("plan")
+            .description("Preview the changes a YAML server config would make.")
+            .add_option(config_option),
+        CreateCommand::new("export")
+            .description("Generate a YAML config reflecting the server's current state."),
+    ]
+}
+
 #[async_trait]
 impl EventHandler for Handler {
     /// Handles incoming messages and attempts to parse them as commands.
@@ -70,8 +184,19 @@ impl EventHandler for Handler {
         }
     }
 
+    /// Handles incoming slash command interactions.
+    async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
+        if let Err(err) = parse_interaction(self, ctx, interaction).await {
+            eprintln!("Error processing interaction: {:?}", err);
+        }
+    }
+
     /// Called when the bot is ready and connected to Discord.
-    async fn ready(&self, _ctx: Context, _: Ready) {
+    async fn ready(&self, ctx: Context, _: Ready) {
         println!("The bot is ready");
+
+        if let Err(err) = Command::set_global_commands(&ctx.http, global_commands()).await {
+            eprintln!("Failed to register slash commands: {err:?}");
+        }
     }
 }
@@ -8,10 +8,18 @@ async fn main() {
 
     let token = env::var("DISCORD_TOKEN").expect("Expected a token in the environment");
 
-    let intents = GatewayIntents::GUILD_MESSAGES | GatewayIntents::MESSAGE_CONTENT;
+    // Prefix commands need the privileged MESSAGE_CONTENT intent; slash commands don't. Flip this
+    // off to run with just GatewayIntents::GUILDS if the bot only needs to handle slash commands.
+    let message_commands = true;
+
+    let mut intents = GatewayIntents::GUILDS;
+    if message_commands {
+        intents |= GatewayIntents::GUILD_MESSAGES | GatewayIntents::MESSAGE_CONTENT;
+    }
 
     let handler = Handler {
         prefix: "~",
+        message_commands,
         ..Default::default()
     };
 
@@ -2,51 +2,127 @@
 //! The config allows for setting up server name, categories, and channels. Some fields are optional
 //! to provide flexibility in the configuration.
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// The main configuration structure for the Discord server.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct Config {
     /// Configuration related to the server.
     pub server: ServerConfig,
 }
 
 /// Configuration for the server, including its name and categories.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct ServerConfig {
     /// The name of the server.
     pub name: String,
     /// A map of category names to their respective configurations.
     pub categories: HashMap<String, CategoryConfig>,
     /// An optional description of the server.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     /// An optional icon URL for the server.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub icon_url: Option<String>,
+    /// Whether to delete categories and channels that are not present in this config.
+    /// Defaults to `false` so existing servers don't lose channels unexpectedly.
+    #[serde(default)]
+    pub prune: bool,
+    /// A map of role names to their respective configurations.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub roles: HashMap<String, RoleConfig>,
+}
+
+/// Configuration for a guild role.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RoleConfig {
+    /// The role's color, as an RGB integer (e.g. `0xff0000` for red).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<u32>,
+    /// Whether the role is displayed separately in the member list.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hoist: Option<bool>,
+    /// Whether the role can be mentioned by members without the mention-everyone permission.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mentionable: Option<bool>,
+    /// The role's permissions, as a Discord permission bitflag value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub permissions: Option<u64>,
+}
+
+/// A single role's allow/deny permission overwrite on a category or channel.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PermissionOverwriteConfig {
+    /// Permissions explicitly granted to the role, as a Discord permission bitflag value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow: Option<u64>,
+    /// Permissions explicitly denied to the role, as a Discord permission bitflag value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deny: Option<u64>,
 }
 
 /// Configuration for a category, including its channels.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct CategoryConfig {
     /// A map of channel names to their respective configurations.
     pub channels: HashMap<String, ChannelConfig>,
     /// An optional description of the category.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     /// Whether the category is marked as NSFW.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub nsfw: Option<bool>,
+    /// Per-role permission overwrites, keyed by role name.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub permissions: HashMap<String, PermissionOverwriteConfig>,
 }
 
 /// Configuration for an individual channel.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct ChannelConfig {
     /// The name of the channel.
     pub name: String,
+    /// The kind of channel this is. Defaults to a text channel.
+    #[serde(default)]
+    pub kind: ChannelKind,
     /// An optional topic for the channel.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub topic: Option<String>,
     /// Whether the channel is marked as NSFW.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub nsfw: Option<bool>,
     /// The position of the channel within the category.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub position: Option<u32>,
     /// Optional ID of the parent category if this is a sub-channel.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub parent_category: Option<String>,
+    /// The bitrate, in bits per second, to use for a voice channel.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bitrate: Option<u32>,
+    /// The maximum number of members allowed in a voice channel at once.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_limit: Option<u32>,
+    /// Per-role permission overwrites, keyed by role name. Channels that omit this inherit their
+    /// category's overwrites, mirroring Discord's "Sync to category" behavior.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub permissions: HashMap<String, PermissionOverwriteConfig>,
+}
+
+/// The kind of channel to create or manage, as written in the config's `kind` field.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChannelKind {
+    /// A standard text channel. The default when `kind` is omitted.
+    #[default]
+    Text,
+    /// A voice channel, which may also carry `bitrate` and `user_limit`.
+    Voice,
+    /// An announcement (news) channel.
+    Announcement,
+    /// A forum channel.
+    Forum,
+    /// A stage channel.
+    Stage,
 }